@@ -0,0 +1,17 @@
+//! The default word list bundled directly into the binary so the tool
+//! works without a `words.txt` sitting next to it, gated behind the
+//! `builtin` feature since it grows the binary size.
+
+#[cfg(feature = "builtin")]
+const BUILTIN_WORDS: &str = include_str!("../assets/words.txt");
+
+/// Returns the embedded dictionary, one word per line, with the trailing
+/// blank line stripped.
+#[cfg(feature = "builtin")]
+pub fn builtin_dictionary() -> Vec<String> {
+    BUILTIN_WORDS
+        .lines()
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
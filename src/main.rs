@@ -1,11 +1,20 @@
-use clap::Parser;
+mod dictionary;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use colored::Colorize;
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+use serde::Serialize;
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Result};
+use std::io::{self, BufRead, BufReader, IsTerminal, Result, Write};
+use std::time::Instant;
 
 const DEFAULT_MAX_GUESSES: usize = 6;
+#[cfg(not(feature = "builtin"))]
+const DEFAULT_DICTIONARY_PATH: &str = "words.txt";
 
 fn load_word_list(file_path: &str) -> Result<Vec<String>> {
     let file = File::open(file_path)?;
@@ -18,20 +27,124 @@ fn load_word_list(file_path: &str) -> Result<Vec<String>> {
     Ok(words)
 }
 
+/// Loads the dictionary to solve with: an explicit `--dictionary` path
+/// always wins, otherwise the embedded word list is used when built with
+/// the `builtin` feature, falling back to `words.txt` in the working
+/// directory when it is not.
+fn load_dictionary(dictionary_path: Option<&str>) -> Vec<String> {
+    if let Some(path) = dictionary_path {
+        return load_word_list(path).expect("Invalid file path.");
+    }
+
+    #[cfg(feature = "builtin")]
+    {
+        dictionary::builtin_dictionary()
+    }
+
+    #[cfg(not(feature = "builtin"))]
+    {
+        load_word_list(DEFAULT_DICTIONARY_PATH).expect("Invalid file path.")
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The box of words, separated by commas. An example box would be "abc,def,ghi,jkl".
+    /// Required unless `--interactive` is set.
     #[arg(short, long)]
-    grid: String,
+    grid: Option<String>,
 
     /// The maximum number of guesses to make
     #[arg(short, long)]
     max_guesses: Option<usize>,
+
+    /// List every minimal solution, ranked best-first, instead of just the first one found
+    #[arg(short, long)]
+    all: bool,
+
+    /// Limit the number of ranked solutions printed when `--all` is set
+    #[arg(short, long)]
+    limit: Option<usize>,
+
+    /// Path to a dictionary file, one word per line. Defaults to the
+    /// embedded word list when built with the `builtin` feature, otherwise
+    /// to `words.txt` in the current directory.
+    #[arg(short, long)]
+    dictionary: Option<String>,
+
+    /// Launch an interactive REPL for setting the grid and exploring solutions
+    #[arg(short, long)]
+    interactive: bool,
+
+    /// When to colorize solution output by grid side
+    #[arg(long, value_enum, default_value = "auto")]
+    color: ColorMode,
+
+    /// Number of threads to use for parallel word filtering and search. Enables parallel mode.
+    #[arg(long)]
+    threads: Option<usize>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve many puzzles and report aggregate statistics
+    Bench(BenchArgs),
+}
+
+/// Options for the `bench` subcommand.
+#[derive(clap::Args, Debug, Clone)]
+struct BenchArgs {
+    /// A file with one grid per line (e.g. "abc,def,ghi,jkl"). Random grids are generated when omitted.
+    #[arg(short, long)]
+    grids: Option<String>,
+
+    /// Number of random grids to generate when `--grids` is not given
+    #[arg(short, long, default_value_t = 100)]
+    count: usize,
+
+    /// The maximum number of guesses to make per puzzle
+    #[arg(short, long)]
+    max_guesses: Option<usize>,
+
+    /// Path to a dictionary file, one word per line
+    #[arg(short, long)]
+    dictionary: Option<String>,
+
+    /// Print the report as JSON instead of human-readable text
+    #[arg(long)]
+    json: bool,
+}
+
+/// Controls whether solution words are colorized by the `Side` each letter
+/// comes from.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// Resolves the mode against the current output stream: `Auto` only
+    /// colorizes when stdout is a TTY.
+    fn should_colorize(self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => io::stdout().is_terminal(),
+        }
+    }
 }
 
 fn is_valid_args_length(args: &Args) -> bool {
-    args.grid.split(',').count() == 4
+    args.grid
+        .as_deref()
+        .map(|grid| grid.split(',').count() == 4)
+        .unwrap_or(false)
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -42,14 +155,62 @@ enum Side {
     Left,
 }
 
+impl Side {
+    /// The ANSI color used to render letters from this side, so consecutive
+    /// letters in a solution are easy to tell apart at a glance.
+    fn color(self) -> colored::Color {
+        match self {
+            Side::Top => colored::Color::Red,
+            Side::Right => colored::Color::Green,
+            Side::Bottom => colored::Color::Yellow,
+            Side::Left => colored::Color::Blue,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Grid {
     words: HashMap<Side, Vec<char>>,
     dictionary: Vec<String>,
     all_letters: HashSet<char>,
+    letter_bits: HashMap<char, u8>,
     max_guesses: usize,
 }
 
+/// A valid word reduced to what the search actually needs: its letter
+/// coverage as a bitmask, and the first/last letters that determine which
+/// words can chain onto it.
+#[derive(Debug, Clone, Copy)]
+struct WordInfo {
+    mask: u16,
+    first: char,
+    last: char,
+}
+
+/// A search state: the last letter played and the letters covered so far.
+type SearchState = (char, u16);
+
+/// Back-pointers from a state to the word index that reached it and the
+/// previous state, used to reconstruct the winning path once the goal is
+/// popped.
+type BackPointers = HashMap<SearchState, (usize, Option<SearchState>)>;
+
+/// The mutable working set of an in-progress Dijkstra search, bundled so it
+/// can be seeded in more than one way (sequentially from a list of seeds, or
+/// with the first layer computed in parallel) before handing off to the
+/// shared `run_dijkstra` continuation.
+#[derive(Default)]
+struct Frontier {
+    /// Lowest cost seen so far for each state, used to avoid pushing
+    /// strictly worse duplicates onto the heap.
+    best: HashMap<SearchState, usize>,
+    /// State -> (word index that reached it, previous state).
+    came_from: BackPointers,
+    /// States settled so far, bucketed by last_char, for dominance pruning.
+    settled: HashMap<char, Vec<(u16, usize)>>,
+    heap: BinaryHeap<Reverse<(usize, char, u16)>>,
+}
+
 impl Grid {
     fn new(grid: String, dictionary: Vec<String>, max_guesses: Option<usize>) -> Self {
         let sides = [Side::Top, Side::Right, Side::Bottom, Side::Left];
@@ -57,15 +218,24 @@ impl Grid {
 
         let mut all_letters = HashSet::new();
         for (side, word) in sides.iter().zip(grid.split(',')) {
-            let chars: Vec<char> = word.chars().collect();
+            let chars: Vec<char> = word.chars().map(|ch| ch.to_ascii_uppercase()).collect();
             all_letters.extend(&chars);
             words.insert(*side, chars);
         }
 
+        let mut sorted_letters: Vec<char> = all_letters.iter().copied().collect();
+        sorted_letters.sort_unstable();
+        let letter_bits = sorted_letters
+            .into_iter()
+            .enumerate()
+            .map(|(bit, letter)| (letter, bit as u8))
+            .collect();
+
         Self {
             words,
             dictionary,
             all_letters,
+            letter_bits,
             max_guesses: max_guesses.unwrap_or(DEFAULT_MAX_GUESSES),
         }
     }
@@ -79,7 +249,18 @@ impl Grid {
     fn generate_words(&self) -> Vec<String> {
         self.dictionary
             .iter()
-            .filter(|&&ref word| self.is_valid_word(word))
+            .filter(|&word| self.is_valid_word(word))
+            .cloned()
+            .collect()
+    }
+
+    /// Parallel counterpart of `generate_words`, filtering the dictionary
+    /// across threads with rayon. Worth the overhead once the dictionary is
+    /// large enough that filtering it dominates solve time.
+    fn generate_words_parallel(&self) -> Vec<String> {
+        self.dictionary
+            .par_iter()
+            .filter(|word| self.is_valid_word(word))
             .cloned()
             .collect()
     }
@@ -106,64 +287,256 @@ impl Grid {
     }
 
     fn get_side(&self, letter: &char) -> Option<Side> {
+        let letter = letter.to_ascii_uppercase();
         for (side, letters) in &self.words {
-            if letters.contains(letter) {
+            if letters.contains(&letter) {
                 return Some(*side);
             }
         }
         None
     }
 
+    /// Renders a word with each letter color-coded by the side it comes
+    /// from, so it's easy to eyeball that consecutive letters alternate
+    /// sides. Falls back to plain text when `colorize` is false.
+    fn render_word(&self, word: &str, colorize: bool) -> String {
+        word.chars()
+            .map(|letter| match self.get_side(&letter) {
+                Some(side) if colorize => letter.to_string().color(side.color()).to_string(),
+                _ => letter.to_string(),
+            })
+            .collect()
+    }
+
+    /// Renders a full solution as space-separated, color-coded words.
+    fn render_solution(&self, solution: &[String], colorize: bool) -> String {
+        solution
+            .iter()
+            .map(|word| self.render_word(word, colorize))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     fn solve(&self) -> Option<Vec<String>> {
-        let valid_words = self.generate_words();
+        self.solve_with_words(&self.generate_words())
+    }
+
+    /// Same as `solve`, but takes an already-generated word list so callers
+    /// that solve repeatedly against the same grid (e.g. the interactive
+    /// REPL) can reuse it instead of regenerating it on every call.
+    fn solve_with_words(&self, valid_words: &[String]) -> Option<Vec<String>> {
+        let solution = self.solve_bfs(valid_words)?;
+        self.is_solution_valid(&solution).then_some(solution)
+    }
+
+    /// Parallel counterpart of `solve`, using rayon for both word filtering
+    /// and the search itself. See [`Grid::solve_bfs_parallel`].
+    fn solve_parallel(&self) -> Option<Vec<String>> {
+        let valid_words = self.generate_words_parallel();
 
-        let solution = self.solve_bfs(&valid_words);
-        if let Some(solution) = solution {
-            if self.is_solution_valid(&solution) {
-                return Some(solution);
+        let solution = self.solve_bfs_parallel(&valid_words)?;
+        self.is_solution_valid(&solution).then_some(solution)
+    }
+
+    /// Maps a word onto the 12-bit coverage mask of `letter_bits`, ignoring
+    /// any letter that (for some reason) is not part of the grid.
+    fn letter_mask(&self, word: &str) -> u16 {
+        let mut mask = 0u16;
+        for ch in word.chars() {
+            if let Some(&bit) = self.letter_bits.get(&ch.to_ascii_uppercase()) {
+                mask |= 1 << bit;
             }
         }
+        mask
+    }
 
-        None
+    /// Whether `(last_char, mask)` is dominated by an already-settled state
+    /// with the same `last_char` whose mask is a superset of `mask` at
+    /// equal-or-lower cost. A dominated state can never lead to a shorter
+    /// solution than the state that dominates it, so it is safe to prune.
+    fn is_dominated(
+        settled: &HashMap<char, Vec<(u16, usize)>>,
+        last_char: char,
+        mask: u16,
+        cost: usize,
+    ) -> bool {
+        settled.get(&last_char).is_some_and(|states| {
+            states
+                .iter()
+                .any(|&(seen_mask, seen_cost)| seen_mask & mask == mask && seen_cost <= cost)
+        })
     }
 
+    /// Uniform-cost search (Dijkstra) over states `(last_char, covered_mask)`,
+    /// where the cost of a state is the number of words used to reach it and
+    /// the goal is covering every letter in the grid. This guarantees the
+    /// returned solution uses the fewest possible words, unlike scanning
+    /// paths in an arbitrary order.
     fn solve_bfs(&self, valid_words: &[String]) -> Option<Vec<String>> {
-        let mut heap = BinaryHeap::new();
+        if valid_words.is_empty() || self.letter_bits.is_empty() {
+            return None;
+        }
 
-        for word in valid_words {
-            let mut used_letters = HashSet::new();
-            for ch in word.chars() {
-                used_letters.insert(ch);
+        let (word_info, words_by_first) = Self::build_word_index(valid_words, &self.letter_mask_fn());
+        let seeds: Vec<usize> = (0..word_info.len()).collect();
+        let indices = Self::dijkstra_from_seeds(
+            &word_info,
+            &words_by_first,
+            &seeds,
+            self.max_guesses,
+            (1u16 << self.letter_bits.len()) - 1,
+        )?;
+        Some(Self::resolve_path(valid_words, &indices))
+    }
+
+    /// Parallel counterpart of `solve_bfs`: seeds the exact same single
+    /// search as `dijkstra_from_seeds`, then expands the first layer — every
+    /// seed word's successors — across threads with rayon before handing
+    /// the rest of the search back to the ordinary sequential Dijkstra loop.
+    /// Seeding every word at once is the single biggest fan-out step in the
+    /// whole search (every word against every word), so it is the one worth
+    /// spending threads on; later layers collapse quickly under dominance
+    /// pruning and stay sequential.
+    fn solve_bfs_parallel(&self, valid_words: &[String]) -> Option<Vec<String>> {
+        if valid_words.is_empty() || self.letter_bits.is_empty() {
+            return None;
+        }
+
+        let (word_info, words_by_first) = Self::build_word_index(valid_words, &self.letter_mask_fn());
+        let full_mask: u16 = (1u16 << self.letter_bits.len()) - 1;
+
+        let mut frontier = Frontier::default();
+        for (index, info) in word_info.iter().enumerate() {
+            let state = (info.last, info.mask);
+            if frontier.best.get(&state).is_none_or(|&cost| 1 < cost) {
+                frontier.best.insert(state, 1);
+                frontier.came_from.insert(state, (index, None));
+            }
+        }
+
+        if let Some((&state, _)) = frontier.came_from.iter().find(|&(&(_, mask), _)| mask == full_mask) {
+            let path = Self::reconstruct_path(&frontier.came_from, state);
+            return Some(Self::resolve_path(valid_words, &path));
+        }
+
+        if self.max_guesses <= 1 {
+            return None; // Seeds are at the guess limit and may not be expanded.
+        }
+
+        for &(last_char, mask) in frontier.came_from.keys() {
+            frontier.settled.entry(last_char).or_default().push((mask, 1));
+        }
+
+        let seed_states: Vec<SearchState> = frontier.came_from.keys().copied().collect();
+        let expansions: Vec<_> = seed_states
+            .par_iter()
+            .flat_map(|&state| Self::expand_state(&word_info, &words_by_first, state, 1))
+            .collect();
+
+        for (next_index, next_state, next_cost, from_state) in expansions {
+            if frontier.best.get(&next_state).is_none_or(|&best_cost| next_cost < best_cost) {
+                frontier.best.insert(next_state, next_cost);
+                frontier.came_from.insert(next_state, (next_index, Some(from_state)));
+                frontier.heap.push(Reverse((next_cost, next_state.0, next_state.1)));
             }
-            let mut used_letters_vec: Vec<char> = used_letters.iter().copied().collect();
-            used_letters_vec.sort_unstable();
-            heap.push(Reverse((1, used_letters_vec, vec![word.clone()])));
         }
 
-        while let Some(Reverse((count, used_letters_vec, path))) = heap.pop() {
-            let used_letters: HashSet<char> = used_letters_vec.iter().copied().collect();
-            if used_letters.len() == self.all_letters.len() {
-                return Some(path);
+        let indices = Self::run_dijkstra(&word_info, &words_by_first, &mut frontier, self.max_guesses, full_mask)?;
+        Some(Self::resolve_path(valid_words, &indices))
+    }
+
+    /// Builds the per-word coverage mask and first/last letters, plus the
+    /// bucket of word indices by first letter, shared by every search
+    /// variant.
+    fn build_word_index(
+        valid_words: &[String],
+        letter_mask: &impl Fn(&str) -> u16,
+    ) -> (Vec<WordInfo>, HashMap<char, Vec<usize>>) {
+        let word_info: Vec<WordInfo> = valid_words
+            .iter()
+            .map(|word| {
+                let chars: Vec<char> = word.chars().map(|ch| ch.to_ascii_uppercase()).collect();
+                WordInfo {
+                    mask: letter_mask(word),
+                    first: *chars.first().unwrap(),
+                    last: *chars.last().unwrap(),
+                }
+            })
+            .collect();
+
+        let mut words_by_first: HashMap<char, Vec<usize>> = HashMap::new();
+        for (index, info) in word_info.iter().enumerate() {
+            words_by_first.entry(info.first).or_default().push(index);
+        }
+
+        (word_info, words_by_first)
+    }
+
+    /// Wraps `letter_mask` as a plain closure so it can be passed into
+    /// associated functions that do not hold a `&self`.
+    fn letter_mask_fn(&self) -> impl Fn(&str) -> u16 + '_ {
+        move |word: &str| self.letter_mask(word)
+    }
+
+    /// Uniform-cost search (Dijkstra) over states `(last_char, covered_mask)`
+    /// starting only from `seeds`, returning the word-index path of the
+    /// shortest chain that covers `full_mask`, if any.
+    fn dijkstra_from_seeds(
+        word_info: &[WordInfo],
+        words_by_first: &HashMap<char, Vec<usize>>,
+        seeds: &[usize],
+        max_guesses: usize,
+        full_mask: u16,
+    ) -> Option<Vec<usize>> {
+        let mut frontier = Frontier::default();
+
+        for &index in seeds {
+            let info = word_info[index];
+            let state = (info.last, info.mask);
+            if frontier.best.get(&state).is_none_or(|&cost| 1 < cost) {
+                frontier.best.insert(state, 1);
+                frontier.came_from.insert(state, (index, None));
+                frontier.heap.push(Reverse((1, info.last, info.mask)));
+            }
+        }
+
+        Self::run_dijkstra(word_info, words_by_first, &mut frontier, max_guesses, full_mask)
+    }
+
+    /// Drains `frontier.heap`, relaxing each popped state's successors, until
+    /// the goal is reached or the search is exhausted. Shared by
+    /// `dijkstra_from_seeds` and `solve_bfs_parallel`, which differ only in
+    /// how the initial layer of `frontier` is populated before the search
+    /// starts.
+    fn run_dijkstra(
+        word_info: &[WordInfo],
+        words_by_first: &HashMap<char, Vec<usize>>,
+        frontier: &mut Frontier,
+        max_guesses: usize,
+        full_mask: u16,
+    ) -> Option<Vec<usize>> {
+        while let Some(Reverse((cost, last_char, mask))) = frontier.heap.pop() {
+            let state = (last_char, mask);
+            if frontier.best.get(&state).is_some_and(|&best_cost| cost > best_cost) {
+                continue; // Stale heap entry, a cheaper path was already found.
+            }
+
+            if mask == full_mask {
+                return Some(Self::reconstruct_path(&frontier.came_from, state));
             }
 
-            if count >= self.max_guesses {
+            if cost >= max_guesses || Self::is_dominated(&frontier.settled, last_char, mask, cost) {
                 continue;
             }
+            frontier.settled.entry(last_char).or_default().push((mask, cost));
 
-            for word in valid_words {
-                if word.chars().next().unwrap() == path.last().unwrap().chars().last().unwrap()
-                    && !path.contains(word)
-                {
-                    let mut new_used_letters = used_letters.clone();
-                    for ch in word.chars() {
-                        new_used_letters.insert(ch);
-                    }
-                    let mut new_used_letters_vec: Vec<char> =
-                        new_used_letters.iter().copied().collect();
-                    new_used_letters_vec.sort_unstable();
-                    let mut new_path = path.clone();
-                    new_path.push(word.clone());
-                    heap.push(Reverse((count + 1, new_used_letters_vec, new_path)));
+            for (next_index, next_state, next_cost, from_state) in
+                Self::expand_state(word_info, words_by_first, state, cost)
+            {
+                if frontier.best.get(&next_state).is_none_or(|&best_cost| next_cost < best_cost) {
+                    frontier.best.insert(next_state, next_cost);
+                    frontier.came_from.insert(next_state, (next_index, Some(from_state)));
+                    frontier.heap.push(Reverse((next_cost, next_state.0, next_state.1)));
                 }
             }
         }
@@ -171,11 +544,202 @@ impl Grid {
         None
     }
 
+    /// The successors of a single search state: every word starting with
+    /// `state`'s last letter, paired with the state and cost it leads to.
+    /// Pure function of its inputs so it can be called from either the
+    /// sequential loop or a rayon `par_iter` over several states at once.
+    fn expand_state(
+        word_info: &[WordInfo],
+        words_by_first: &HashMap<char, Vec<usize>>,
+        state: SearchState,
+        cost: usize,
+    ) -> Vec<(usize, SearchState, usize, SearchState)> {
+        let (last_char, mask) = state;
+        let Some(next_indices) = words_by_first.get(&last_char) else {
+            return Vec::new();
+        };
+
+        next_indices
+            .iter()
+            .map(|&next_index| {
+                let next_info = word_info[next_index];
+                let new_mask = mask | next_info.mask;
+                let new_state = (next_info.last, new_mask);
+                (next_index, new_state, cost + 1, state)
+            })
+            .collect()
+    }
+
+    /// Maps a word-index path back onto the owned dictionary words.
+    fn resolve_path(valid_words: &[String], indices: &[usize]) -> Vec<String> {
+        indices.iter().map(|&index| valid_words[index].clone()).collect()
+    }
+
+    /// Walks the back-pointer chain from `goal` to the seed word, producing
+    /// the word-index path in play order.
+    fn reconstruct_path(
+        came_from: &BackPointers,
+        goal: SearchState,
+    ) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut current = Some(goal);
+        while let Some(state) = current {
+            let (word_index, previous) = came_from[&state];
+            path.push(word_index);
+            current = previous;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Collects every minimal-length solution, ranked best-first
+    fn solve_all(&self) -> Vec<Vec<String>> {
+        self.solve_all_with_words(&self.generate_words())
+    }
+
+    /// Same as `solve_all`, but takes an already-generated word list. See
+    /// `solve_with_words`.
+    fn solve_all_with_words(&self, valid_words: &[String]) -> Vec<Vec<String>> {
+        let Some(min_word_count) = self.solve_bfs(valid_words).map(|solution| solution.len())
+        else {
+            return Vec::new();
+        };
+
+        let mut solutions = if min_word_count == 2 {
+            self.two_word_solutions(valid_words)
+        } else {
+            self.solutions_of_length(valid_words, min_word_count)
+        };
+
+        solutions.retain(|solution| self.is_solution_valid(solution));
+        Self::rank_solutions(&mut solutions);
+        solutions
+    }
+
+    /// All two-word chains that together cover the full letter set
+    fn two_word_solutions(&self, valid_words: &[String]) -> Vec<Vec<String>> {
+        let full_mask: u16 = (1u16 << self.letter_bits.len()) - 1;
+
+        let mut by_first: HashMap<char, Vec<(&String, u16)>> = HashMap::new();
+        for word in valid_words {
+            let first = word.chars().next().unwrap();
+            by_first
+                .entry(first)
+                .or_default()
+                .push((word, self.letter_mask(word)));
+        }
+
+        let mut solutions = Vec::new();
+        for first_word in valid_words {
+            let first_mask = self.letter_mask(first_word);
+            let last_char = first_word.chars().last().unwrap();
+            if let Some(candidates) = by_first.get(&last_char) {
+                for &(second_word, second_mask) in candidates {
+                    if first_mask | second_mask == full_mask {
+                        solutions.push(vec![first_word.clone(), second_word.clone()]);
+                    }
+                }
+            }
+        }
+        solutions
+    }
+
+    /// Backtracking search for every chain of exactly `length` words covering every letter
+    fn solutions_of_length(&self, valid_words: &[String], length: usize) -> Vec<Vec<String>> {
+        let full_mask: u16 = (1u16 << self.letter_bits.len()) - 1;
+        let (word_info, words_by_first) = Self::build_word_index(valid_words, &self.letter_mask_fn());
+
+        let mut solutions = Vec::new();
+        let mut path = Vec::new();
+        let mut used = HashSet::new();
+        for start_index in 0..word_info.len() {
+            path.push(start_index);
+            used.insert(start_index);
+            Self::extend_solution(
+                &word_info,
+                &words_by_first,
+                &mut path,
+                &mut used,
+                length,
+                full_mask,
+                &mut solutions,
+            );
+            used.remove(&start_index);
+            path.pop();
+        }
+
+        solutions
+            .into_iter()
+            .map(|indices: Vec<usize>| {
+                indices
+                    .into_iter()
+                    .map(|index| valid_words[index].clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn extend_solution(
+        word_info: &[WordInfo],
+        words_by_first: &HashMap<char, Vec<usize>>,
+        path: &mut Vec<usize>,
+        used: &mut HashSet<usize>,
+        length: usize,
+        full_mask: u16,
+        solutions: &mut Vec<Vec<usize>>,
+    ) {
+        let mask = path
+            .iter()
+            .fold(0u16, |mask, &index| mask | word_info[index].mask);
+
+        if path.len() == length {
+            if mask == full_mask {
+                solutions.push(path.clone());
+            }
+            return;
+        }
+
+        let last_char = word_info[*path.last().unwrap()].last;
+        if let Some(next_indices) = words_by_first.get(&last_char) {
+            for &next_index in next_indices {
+                if used.contains(&next_index) {
+                    continue; // Each word can only appear once per solution.
+                }
+                path.push(next_index);
+                used.insert(next_index);
+                Self::extend_solution(
+                    word_info,
+                    words_by_first,
+                    path,
+                    used,
+                    length,
+                    full_mask,
+                    solutions,
+                );
+                used.remove(&next_index);
+                path.pop();
+            }
+        }
+    }
+
+    /// Sorts solutions best-first: fewest repeated letters, then fewest total letters
+    fn rank_solutions(solutions: &mut [Vec<String>]) {
+        solutions.sort_by_key(|solution| {
+            let total_letters: usize = solution.iter().map(|word| word.len()).sum();
+            let distinct_letters = solution
+                .iter()
+                .flat_map(|word| word.chars())
+                .collect::<HashSet<_>>()
+                .len();
+            (total_letters.saturating_sub(distinct_letters), total_letters)
+        });
+    }
+
     fn is_solution_valid(&self, solution: &[String]) -> bool {
         let mut used_letters = HashSet::new();
         for word in solution {
             for ch in word.chars() {
-                used_letters.insert(ch);
+                used_letters.insert(ch.to_ascii_uppercase());
             }
         }
         used_letters == self.all_letters
@@ -185,25 +749,367 @@ impl Grid {
 fn main() {
     let args = Args::parse();
 
+    if let Some(Command::Bench(bench_args)) = args.command {
+        run_bench(bench_args);
+        return;
+    }
+
+    let dictionary = load_dictionary(args.dictionary.as_deref());
+    let colorize = args.color.should_colorize();
+    let parallel = args.threads.is_some();
+
+    if let Some(threads) = args.threads {
+        // Ignore the error: a previous call (e.g. in tests) may already
+        // have initialized the global pool.
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global();
+    }
+
+    if args.interactive {
+        run_interactive(dictionary, args.max_guesses, colorize);
+        return;
+    }
+
     if !is_valid_args_length(&args) {
         println!("Invalid grid formation. Use `--help` to see the correct format.");
         return;
     }
 
-    let dictionary = load_word_list("words.txt").expect("Invalid file path.");
-    let game = Grid::new(args.grid.to_uppercase(), dictionary, args.max_guesses);
+    let game = Grid::new(args.grid.unwrap().to_uppercase(), dictionary, args.max_guesses);
 
     if !game.is_valid() {
         println!("Invalid grid formation. Use `--help` to see the correct format.");
         return;
     }
 
-    match game.solve() {
-        Some(solution) => println!("Solution found: {:?}", solution),
+    if args.all {
+        let solutions = game.solve_all();
+        if solutions.is_empty() {
+            println!("No solution found.");
+            return;
+        }
+        let limit = args.limit.unwrap_or(solutions.len());
+        for (rank, solution) in solutions.iter().take(limit).enumerate() {
+            println!("{}. {}", rank + 1, game.render_solution(solution, colorize));
+        }
+        return;
+    }
+
+    let solution = if parallel {
+        game.solve_parallel()
+    } else {
+        game.solve()
+    };
+
+    match solution {
+        Some(solution) => println!(
+            "Solution found: {}",
+            game.render_solution(&solution, colorize)
+        ),
         None => println!("No solution found."),
     }
 }
 
+/// Runs an interactive prompt for trying grids and inspecting solutions
+/// without restarting the binary for every guess. `Grid` is rebuilt on
+/// demand whenever the grid or `max_guesses` changes, and the generated
+/// word list is cached between queries so repeated solves are instant.
+fn run_interactive(dictionary: Vec<String>, max_guesses: Option<usize>, colorize: bool) {
+    println!("Letter Boxed interactive mode. Type `help` for a list of commands.");
+
+    let mut max_guesses = max_guesses;
+    let mut current_grid: Option<String> = None;
+    let mut grid_history: Vec<String> = Vec::new();
+    let mut game: Option<Grid> = None;
+    let mut cached_words: Option<Vec<String>> = None;
+    let mut cached_solutions: Option<Vec<Vec<String>>> = None;
+    let mut next_solution_index: usize = 0;
+
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        if io::stdout().flush().is_err() {
+            break;
+        }
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break; // EOF
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut parts = line.splitn(2, ' ');
+        let command = parts.next().unwrap_or("");
+        let argument = parts.next().unwrap_or("").trim();
+
+        match command {
+            "grid" => {
+                if argument.split(',').count() != 4 {
+                    println!("Invalid grid formation. Example: \"abc,def,ghi,jkl\".");
+                    continue;
+                }
+                if let Some(previous) = current_grid.take() {
+                    grid_history.push(previous);
+                }
+                let new_game = Grid::new(argument.to_uppercase(), dictionary.clone(), max_guesses);
+                if new_game.is_valid() {
+                    println!("Grid set to \"{}\".", argument);
+                } else {
+                    println!(
+                        "Warning: grid must have 4 sides of 3 distinct letters covering 12 letters total."
+                    );
+                }
+                current_grid = Some(argument.to_string());
+                game = Some(new_game);
+                cached_words = None;
+                cached_solutions = None;
+                next_solution_index = 0;
+            }
+            "solve" => match &game {
+                Some(game) if game.is_valid() => {
+                    let words = cached_words.get_or_insert_with(|| game.generate_words());
+                    match game.solve_with_words(words) {
+                        Some(solution) => println!(
+                            "Solution found: {}",
+                            game.render_solution(&solution, colorize)
+                        ),
+                        None => println!("No solution found."),
+                    }
+                }
+                Some(_) => println!("Grid is invalid. Use `grid <box>` to set a valid one."),
+                None => println!("No grid set yet. Use `grid <box>` first."),
+            },
+            "next" => match &game {
+                Some(game) if game.is_valid() => {
+                    let words = cached_words.get_or_insert_with(|| game.generate_words());
+                    let solutions =
+                        cached_solutions.get_or_insert_with(|| game.solve_all_with_words(words));
+                    if solutions.is_empty() {
+                        println!("No solution found.");
+                    } else {
+                        let index = next_solution_index % solutions.len();
+                        println!(
+                            "Solution {}/{}: {}",
+                            index + 1,
+                            solutions.len(),
+                            game.render_solution(&solutions[index], colorize)
+                        );
+                        next_solution_index += 1;
+                    }
+                }
+                Some(_) => println!("Grid is invalid. Use `grid <box>` to set a valid one."),
+                None => println!("No grid set yet. Use `grid <box>` first."),
+            },
+            "words" => match &game {
+                Some(game) if game.is_valid() => {
+                    let words = cached_words.get_or_insert_with(|| game.generate_words());
+                    println!("{} valid words: {:?}", words.len(), words);
+                }
+                Some(_) => println!("Grid is invalid. Use `grid <box>` to set a valid one."),
+                None => println!("No grid set yet. Use `grid <box>` first."),
+            },
+            "max-guesses" => match argument.parse::<usize>() {
+                Ok(value) => {
+                    max_guesses = Some(value);
+                    if let Some(grid) = &current_grid {
+                        game = Some(Grid::new(grid.to_uppercase(), dictionary.clone(), max_guesses));
+                        cached_words = None;
+                        cached_solutions = None;
+                        next_solution_index = 0;
+                    }
+                    println!("Max guesses set to {}.", value);
+                }
+                Err(_) => println!("Usage: max-guesses <number>"),
+            },
+            "undo" => match grid_history.pop() {
+                Some(previous) => {
+                    game = Some(Grid::new(previous.to_uppercase(), dictionary.clone(), max_guesses));
+                    cached_words = None;
+                    cached_solutions = None;
+                    next_solution_index = 0;
+                    println!("Reverted to grid \"{}\".", previous);
+                    current_grid = Some(previous);
+                }
+                None => println!("No previous grid to revert to."),
+            },
+            "help" => print_interactive_help(),
+            "quit" | "exit" => break,
+            _ => println!("Unknown command `{}`. Type `help` for a list of commands.", command),
+        }
+    }
+}
+
+fn print_interactive_help() {
+    println!("Commands:");
+    println!("  grid <a,b,c,d>   Set or change the grid");
+    println!("  solve            Solve the current grid");
+    println!("  next             Show the next-best solution for the current grid");
+    println!("  words            List the valid words for the current grid");
+    println!("  max-guesses <n>  Change the maximum number of guesses");
+    println!("  undo             Revert to the previous grid");
+    println!("  help             Show this message");
+    println!("  quit             Exit interactive mode");
+}
+
+/// One puzzle's outcome from a benchmark run.
+#[derive(Debug, Clone, Serialize)]
+struct SolveResult {
+    grid: String,
+    solved: bool,
+    word_count: Option<usize>,
+    elapsed_ms: f64,
+}
+
+/// Aggregate statistics over a batch of `SolveResult`s.
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    puzzles: usize,
+    solved: usize,
+    solve_rate: f64,
+    word_count_distribution: BTreeMap<usize, usize>,
+    mean_words: f64,
+    median_words: f64,
+    p50_ms: f64,
+    p90_ms: f64,
+    p99_ms: f64,
+}
+
+/// Generates a random valid grid: 12 distinct letters drawn from the
+/// alphabet and split evenly across the four sides.
+fn random_grid() -> String {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    letters.shuffle(&mut rand::thread_rng());
+
+    letters
+        .into_iter()
+        .take(12)
+        .collect::<Vec<char>>()
+        .chunks(3)
+        .map(|side| side.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Solves a single grid, timing the search and capturing whether it
+/// succeeded and how many words the solution used.
+fn time_solve(grid_spec: &str, dictionary: &[String], max_guesses: Option<usize>) -> SolveResult {
+    let game = Grid::new(grid_spec.to_uppercase(), dictionary.to_vec(), max_guesses);
+
+    let started = Instant::now();
+    let solution = if game.is_valid() { game.solve() } else { None };
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    SolveResult {
+        grid: grid_spec.to_string(),
+        solved: solution.is_some(),
+        word_count: solution.map(|solution| solution.len()),
+        elapsed_ms,
+    }
+}
+
+/// The value at `percentile` (0-100) of an already-sorted slice: the
+/// element at index `percentile / 100 * (len - 1)`, rounded to the nearest
+/// index, with no interpolation between neighbouring values.
+fn percentile(sorted_values: &[f64], percentile: f64) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+    let rank = ((percentile / 100.0) * (sorted_values.len() - 1) as f64).round() as usize;
+    sorted_values[rank.min(sorted_values.len() - 1)]
+}
+
+fn median(sorted_values: &[usize]) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+    let mid = sorted_values.len() / 2;
+    if sorted_values.len().is_multiple_of(2) {
+        (sorted_values[mid - 1] + sorted_values[mid]) as f64 / 2.0
+    } else {
+        sorted_values[mid] as f64
+    }
+}
+
+fn summarize(results: &[SolveResult]) -> BenchReport {
+    let puzzles = results.len();
+    let solved = results.iter().filter(|result| result.solved).count();
+    let solve_rate = if puzzles == 0 {
+        0.0
+    } else {
+        solved as f64 / puzzles as f64
+    };
+
+    let mut word_counts: Vec<usize> = results.iter().filter_map(|r| r.word_count).collect();
+    let mut word_count_distribution: BTreeMap<usize, usize> = BTreeMap::new();
+    for &count in &word_counts {
+        *word_count_distribution.entry(count).or_insert(0) += 1;
+    }
+    word_counts.sort_unstable();
+
+    let mean_words = if word_counts.is_empty() {
+        0.0
+    } else {
+        word_counts.iter().sum::<usize>() as f64 / word_counts.len() as f64
+    };
+
+    let mut elapsed_ms: Vec<f64> = results.iter().map(|result| result.elapsed_ms).collect();
+    elapsed_ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    BenchReport {
+        puzzles,
+        solved,
+        solve_rate,
+        word_count_distribution,
+        mean_words,
+        median_words: median(&word_counts),
+        p50_ms: percentile(&elapsed_ms, 50.0),
+        p90_ms: percentile(&elapsed_ms, 90.0),
+        p99_ms: percentile(&elapsed_ms, 99.0),
+    }
+}
+
+fn print_bench_report(report: &BenchReport) {
+    println!("Puzzles solved: {}/{} ({:.1}%)", report.solved, report.puzzles, report.solve_rate * 100.0);
+    println!("Mean words per solution: {:.2}", report.mean_words);
+    println!("Median words per solution: {:.2}", report.median_words);
+    println!("Word count distribution:");
+    for (word_count, occurrences) in &report.word_count_distribution {
+        println!("  {} words: {} puzzles", word_count, occurrences);
+    }
+    println!(
+        "Solve time (ms): p50={:.2} p90={:.2} p99={:.2}",
+        report.p50_ms, report.p90_ms, report.p99_ms
+    );
+}
+
+/// Solves a batch of grids (read from a file or randomly generated) and
+/// reports aggregate statistics.
+fn run_bench(bench_args: BenchArgs) {
+    let dictionary = load_dictionary(bench_args.dictionary.as_deref());
+
+    let grids = match &bench_args.grids {
+        Some(path) => load_word_list(path).expect("Invalid file path."),
+        None => (0..bench_args.count).map(|_| random_grid()).collect(),
+    };
+
+    let results: Vec<SolveResult> = grids
+        .iter()
+        .map(|grid| time_solve(grid, &dictionary, bench_args.max_guesses))
+        .collect();
+
+    let report = summarize(&results);
+
+    if bench_args.json {
+        println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize report."));
+    } else {
+        print_bench_report(&report);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,37 +1119,67 @@ mod tests {
     #[test]
     fn test_grid_is_valid() {
         let grid = Grid::new("abc,def,ghi,jkl".to_string(), EMPTY_DICTIONARY, None);
-        assert_eq!(grid.is_valid(), true);
+        assert!(grid.is_valid());
     }
 
     #[test]
     fn test_is_valid_args_length() {
         let args = Args {
-            grid: "abc,def,ghi,jkl".to_string(),
+            command: None,
+            grid: Some("abc,def,ghi,jkl".to_string()),
             max_guesses: None,
+            all: false,
+            limit: None,
+            dictionary: None,
+            interactive: false,
+            color: ColorMode::Auto,
+            threads: None,
         };
-        assert_eq!(is_valid_args_length(&args), true);
+        assert!(is_valid_args_length(&args));
     }
 
     #[test]
     fn test_is_invalid_args_length() {
         let args = Args {
-            grid: "abc,def,ghi".to_string(),
+            command: None,
+            grid: Some("abc,def,ghi".to_string()),
+            max_guesses: None,
+            all: false,
+            limit: None,
+            dictionary: None,
+            interactive: false,
+            color: ColorMode::Auto,
+            threads: None,
+        };
+        assert!(!is_valid_args_length(&args));
+    }
+
+    #[test]
+    fn test_is_invalid_args_length_without_grid() {
+        let args = Args {
+            command: None,
+            grid: None,
             max_guesses: None,
+            all: false,
+            limit: None,
+            dictionary: None,
+            interactive: true,
+            color: ColorMode::Auto,
+            threads: None,
         };
-        assert_eq!(is_valid_args_length(&args), false);
+        assert!(!is_valid_args_length(&args));
     }
 
     #[test]
     fn test_grid_has_too_few_letters() {
         let grid = Grid::new("ab,def,ghi,jkl".to_string(), EMPTY_DICTIONARY, None);
-        assert_eq!(grid.is_valid(), false);
+        assert!(!grid.is_valid());
     }
 
     #[test]
     fn test_grid_has_too_many_letters() {
         let grid = Grid::new("abcd,def,ghi,jkl".to_string(), EMPTY_DICTIONARY, None);
-        assert_eq!(grid.is_valid(), false);
+        assert!(!grid.is_valid());
     }
 
     #[test]
@@ -278,4 +1214,105 @@ mod tests {
 
         assert_eq!(generated_words, expected_words);
     }
+
+    #[test]
+    fn test_solve_finds_shortest_solution() {
+        let dictionary = vec!["adgjbehk".to_string(), "kcfil".to_string()];
+        let grid = Grid::new("abc,def,ghi,jkl".to_string(), dictionary, None);
+        let solution = grid.solve().expect("expected a solution");
+        assert!(grid.is_solution_valid(&solution));
+        assert_eq!(solution.len(), 2);
+    }
+
+    #[test]
+    fn test_solve_matches_lowercase_dictionary_against_uppercase_grid() {
+        let dictionary = vec!["adgjbehk".to_string(), "kcfil".to_string()];
+        let grid = Grid::new("ABC,DEF,GHI,JKL".to_string(), dictionary, None);
+        let solution = grid.solve().expect("expected a solution");
+        assert!(grid.is_solution_valid(&solution));
+        assert_eq!(solution.len(), 2);
+    }
+
+    #[test]
+    fn test_solve_all_ranks_fewer_repeated_letters_first() {
+        // "adgjbehkc" + "cfkil" repeats two letters and is generated first;
+        // "adgjbehk" + "kcfil" repeats one and must be ranked ahead of it.
+        let dictionary = vec![
+            "adgjbehkc".to_string(),
+            "cfkil".to_string(),
+            "adgjbehk".to_string(),
+            "kcfil".to_string(),
+        ];
+        let grid = Grid::new("abc,def,ghi,jkl".to_string(), dictionary, None);
+        let solutions = grid.solve_all();
+
+        assert!(!solutions.is_empty());
+        assert!(solutions.iter().all(|solution| solution.len() == 2));
+        assert_eq!(solutions[0], vec!["adgjbehk".to_string(), "kcfil".to_string()]);
+    }
+
+    #[test]
+    fn test_render_word_without_colorize_is_plain_text() {
+        let grid = Grid::new("abc,def,ghi,jkl".to_string(), EMPTY_DICTIONARY, None);
+        assert_eq!(grid.render_word("beg", false), "beg".to_string());
+    }
+
+    #[test]
+    fn test_solve_parallel_matches_serial() {
+        let dictionary = vec!["adgjbehk".to_string(), "kcfil".to_string()];
+        let grid = Grid::new("abc,def,ghi,jkl".to_string(), dictionary, None);
+
+        let serial = grid.solve().expect("expected a solution");
+        let parallel = grid.solve_parallel().expect("expected a solution");
+
+        assert!(grid.is_solution_valid(&serial));
+        assert!(grid.is_solution_valid(&parallel));
+        assert_eq!(serial.len(), parallel.len());
+    }
+
+    #[test]
+    fn test_random_grid_has_twelve_distinct_letters() {
+        let grid = random_grid();
+        let sides: Vec<&str> = grid.split(',').collect();
+        assert_eq!(sides.len(), 4);
+        assert!(sides.iter().all(|side| side.len() == 3));
+
+        let distinct_letters: HashSet<char> = grid.chars().filter(|ch| *ch != ',').collect();
+        assert_eq!(distinct_letters.len(), 12);
+    }
+
+    #[test]
+    fn test_summarize_reports_solve_rate_and_distribution() {
+        let results = vec![
+            SolveResult {
+                grid: "abc,def,ghi,jkl".to_string(),
+                solved: true,
+                word_count: Some(2),
+                elapsed_ms: 1.0,
+            },
+            SolveResult {
+                grid: "xyz,def,ghi,jkl".to_string(),
+                solved: false,
+                word_count: None,
+                elapsed_ms: 2.0,
+            },
+        ];
+
+        let report = summarize(&results);
+        assert_eq!(report.puzzles, 2);
+        assert_eq!(report.solved, 1);
+        assert_eq!(report.solve_rate, 0.5);
+        assert_eq!(report.word_count_distribution.get(&2), Some(&1));
+    }
+
+    #[test]
+    #[cfg(feature = "builtin")]
+    fn test_time_solve_against_builtin_dictionary_reports_solved() {
+        let dictionary = dictionary::builtin_dictionary();
+        let result = time_solve("ant,dye,grw,voi", &dictionary, None);
+        assert!(result.solved);
+
+        let report = summarize(&[result]);
+        assert_eq!(report.solve_rate, 1.0);
+    }
 }